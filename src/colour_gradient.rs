@@ -17,6 +17,9 @@
  * along with this program; if not, see <http://www.gnu.org/licenses/>.
  */
 
+use std::fmt;
+use std::io::BufRead;
+
 /// Colours required for a PNG file, includes the alpha channel.
 #[derive(Clone, PartialEq, Debug)]
 pub struct RGBAColour {
@@ -34,6 +37,300 @@ impl RGBAColour {
   pub fn to_vec(&self) -> Vec<u8> {
     vec![self.r, self.g, self.b, self.a]
   }
+
+  /// Like [`RGBAColour::to_vec`], but without the allocation.
+  pub fn to_bytes(&self) -> [u8; 4] {
+    [self.r, self.g, self.b, self.a]
+  }
+
+  /// Parse a `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` hex colour.
+  pub fn from_hex(hex: &str) -> Result<Self, ParseError> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+    let digit = |c: u8| -> Result<u8, ParseError> {
+      (c as char)
+        .to_digit(16)
+        .map(|d| d as u8)
+        .ok_or_else(|| ParseError::InvalidFormat(format!("invalid hex digit in '#{}'", hex)))
+    };
+    let short = |c: u8| -> Result<u8, ParseError> { digit(c).map(|d| d << 4 | d) };
+    let byte = |hi: u8, lo: u8| -> Result<u8, ParseError> { Ok(digit(hi)? << 4 | digit(lo)?) };
+
+    let bytes = hex.as_bytes();
+    match bytes.len() {
+      3 => Ok(Self::new(short(bytes[0])?, short(bytes[1])?, short(bytes[2])?, 255)),
+      4 => Ok(Self::new(
+        short(bytes[0])?,
+        short(bytes[1])?,
+        short(bytes[2])?,
+        short(bytes[3])?,
+      )),
+      6 => Ok(Self::new(
+        byte(bytes[0], bytes[1])?,
+        byte(bytes[2], bytes[3])?,
+        byte(bytes[4], bytes[5])?,
+        255,
+      )),
+      8 => Ok(Self::new(
+        byte(bytes[0], bytes[1])?,
+        byte(bytes[2], bytes[3])?,
+        byte(bytes[4], bytes[5])?,
+        byte(bytes[6], bytes[7])?,
+      )),
+      _ => Err(ParseError::InvalidFormat(format!(
+        "'#{}' is not a valid #rgb, #rgba, #rrggbb or #rrggbbaa colour",
+        hex
+      ))),
+    }
+  }
+}
+
+/// An error produced while parsing a gradient from an external format.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParseError {
+  InvalidFormat(String),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat(msg) => write!(f, "invalid gradient format: {}", msg),
+    }
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The colour space used to interpolate between stops in a [`ColourGradient`].
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Blend {
+  /// Interpolate r, g, b, a independently and linearly (the default).
+  #[default]
+  LinearRgb,
+  /// Interpolate in HSV, taking the shortest way round the hue wheel.
+  Hsv,
+  /// Interpolate in CIE Lab (D65 white point).
+  Lab,
+}
+
+/// A built-in colour map, selectable via [`ColourGradient::preset`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Preset {
+  /// Dark purple to teal to yellow.
+  Viridis,
+  /// Black to purple to orange to pale yellow.
+  Magma,
+  /// Black to purple to red to pale yellow.
+  Inferno,
+  /// A high-contrast, perceptually even rainbow map.
+  Turbo,
+}
+
+/// Canonical control-point colours for each [`Preset`].
+const VIRIDIS: &[(u8, u8, u8)] = &[
+  (0x44, 0x01, 0x54),
+  (0x46, 0x32, 0x7e),
+  (0x36, 0x5c, 0x8d),
+  (0x27, 0x7f, 0x8e),
+  (0x1f, 0xa1, 0x87),
+  (0x4a, 0xc1, 0x6d),
+  (0xa0, 0xda, 0x39),
+  (0xfd, 0xe7, 0x25),
+];
+
+const MAGMA: &[(u8, u8, u8)] = &[
+  (0x00, 0x00, 0x04),
+  (0x22, 0x11, 0x50),
+  (0x5f, 0x18, 0x7f),
+  (0x98, 0x2d, 0x80),
+  (0xd3, 0x43, 0x6e),
+  (0xf8, 0x76, 0x5c),
+  (0xfe, 0xba, 0x80),
+  (0xfc, 0xfd, 0xbf),
+];
+
+const INFERNO: &[(u8, u8, u8)] = &[
+  (0x00, 0x00, 0x04),
+  (0x1f, 0x0c, 0x48),
+  (0x55, 0x0f, 0x6d),
+  (0x88, 0x22, 0x6a),
+  (0xa8, 0x36, 0x55),
+  (0xe3, 0x59, 0x33),
+  (0xfb, 0xa4, 0x0a),
+  (0xfc, 0xff, 0xa4),
+];
+
+const TURBO: &[(u8, u8, u8)] = &[
+  (0x30, 0x12, 0x3b),
+  (0x44, 0x54, 0xc4),
+  (0x44, 0x90, 0xfe),
+  (0x1f, 0xc8, 0xde),
+  (0x29, 0xef, 0xa2),
+  (0x7d, 0xff, 0x56),
+  (0xc2, 0xf6, 0x34),
+  (0xf4, 0xc3, 0x2d),
+  (0xfa, 0x9a, 0x3c),
+  (0xe4, 0x46, 0x0a),
+  (0x7a, 0x04, 0x03),
+];
+
+/// Look up a CSS named colour; `name` must already be lowercase.
+fn css_named_colour(name: &str) -> Option<(u8, u8, u8)> {
+  Some(match name {
+    "aliceblue" => (0xf0, 0xf8, 0xff),
+    "antiquewhite" => (0xfa, 0xeb, 0xd7),
+    "aqua" => (0x00, 0xff, 0xff),
+    "aquamarine" => (0x7f, 0xff, 0xd4),
+    "azure" => (0xf0, 0xff, 0xff),
+    "beige" => (0xf5, 0xf5, 0xdc),
+    "bisque" => (0xff, 0xe4, 0xc4),
+    "black" => (0x00, 0x00, 0x00),
+    "blanchedalmond" => (0xff, 0xeb, 0xcd),
+    "blue" => (0x00, 0x00, 0xff),
+    "blueviolet" => (0x8a, 0x2b, 0xe2),
+    "brown" => (0xa5, 0x2a, 0x2a),
+    "burlywood" => (0xde, 0xb8, 0x87),
+    "cadetblue" => (0x5f, 0x9e, 0xa0),
+    "chartreuse" => (0x7f, 0xff, 0x00),
+    "chocolate" => (0xd2, 0x69, 0x1e),
+    "coral" => (0xff, 0x7f, 0x50),
+    "cornflowerblue" => (0x64, 0x95, 0xed),
+    "cornsilk" => (0xff, 0xf8, 0xdc),
+    "crimson" => (0xdc, 0x14, 0x3c),
+    "cyan" => (0x00, 0xff, 0xff),
+    "darkblue" => (0x00, 0x00, 0x8b),
+    "darkcyan" => (0x00, 0x8b, 0x8b),
+    "darkgoldenrod" => (0xb8, 0x86, 0x0b),
+    "darkgray" | "darkgrey" => (0xa9, 0xa9, 0xa9),
+    "darkgreen" => (0x00, 0x64, 0x00),
+    "darkkhaki" => (0xbd, 0xb7, 0x6b),
+    "darkmagenta" => (0x8b, 0x00, 0x8b),
+    "darkolivegreen" => (0x55, 0x6b, 0x2f),
+    "darkorange" => (0xff, 0x8c, 0x00),
+    "darkorchid" => (0x99, 0x32, 0xcc),
+    "darkred" => (0x8b, 0x00, 0x00),
+    "darksalmon" => (0xe9, 0x96, 0x7a),
+    "darkseagreen" => (0x8f, 0xbc, 0x8f),
+    "darkslateblue" => (0x48, 0x3d, 0x8b),
+    "darkslategray" | "darkslategrey" => (0x2f, 0x4f, 0x4f),
+    "darkturquoise" => (0x00, 0xce, 0xd1),
+    "darkviolet" => (0x94, 0x00, 0xd3),
+    "deeppink" => (0xff, 0x14, 0x93),
+    "deepskyblue" => (0x00, 0xbf, 0xff),
+    "dimgray" | "dimgrey" => (0x69, 0x69, 0x69),
+    "dodgerblue" => (0x1e, 0x90, 0xff),
+    "firebrick" => (0xb2, 0x22, 0x22),
+    "floralwhite" => (0xff, 0xfa, 0xf0),
+    "forestgreen" => (0x22, 0x8b, 0x22),
+    "fuchsia" => (0xff, 0x00, 0xff),
+    "gainsboro" => (0xdc, 0xdc, 0xdc),
+    "ghostwhite" => (0xf8, 0xf8, 0xff),
+    "gold" => (0xff, 0xd7, 0x00),
+    "goldenrod" => (0xda, 0xa5, 0x20),
+    "gray" | "grey" => (0x80, 0x80, 0x80),
+    "green" => (0x00, 0x80, 0x00),
+    "greenyellow" => (0xad, 0xff, 0x2f),
+    "honeydew" => (0xf0, 0xff, 0xf0),
+    "hotpink" => (0xff, 0x69, 0xb4),
+    "indianred" => (0xcd, 0x5c, 0x5c),
+    "indigo" => (0x4b, 0x00, 0x82),
+    "ivory" => (0xff, 0xff, 0xf0),
+    "khaki" => (0xf0, 0xe6, 0x8c),
+    "lavender" => (0xe6, 0xe6, 0xfa),
+    "lavenderblush" => (0xff, 0xf0, 0xf5),
+    "lawngreen" => (0x7c, 0xfc, 0x00),
+    "lemonchiffon" => (0xff, 0xfa, 0xcd),
+    "lightblue" => (0xad, 0xd8, 0xe6),
+    "lightcoral" => (0xf0, 0x80, 0x80),
+    "lightcyan" => (0xe0, 0xff, 0xff),
+    "lightgoldenrodyellow" => (0xfa, 0xfa, 0xd2),
+    "lightgray" | "lightgrey" => (0xd3, 0xd3, 0xd3),
+    "lightgreen" => (0x90, 0xee, 0x90),
+    "lightpink" => (0xff, 0xb6, 0xc1),
+    "lightsalmon" => (0xff, 0xa0, 0x7a),
+    "lightseagreen" => (0x20, 0xb2, 0xaa),
+    "lightskyblue" => (0x87, 0xce, 0xfa),
+    "lightslategray" | "lightslategrey" => (0x77, 0x88, 0x99),
+    "lightsteelblue" => (0xb0, 0xc4, 0xde),
+    "lightyellow" => (0xff, 0xff, 0xe0),
+    "lime" => (0x00, 0xff, 0x00),
+    "limegreen" => (0x32, 0xcd, 0x32),
+    "linen" => (0xfa, 0xf0, 0xe6),
+    "magenta" => (0xff, 0x00, 0xff),
+    "maroon" => (0x80, 0x00, 0x00),
+    "mediumaquamarine" => (0x66, 0xcd, 0xaa),
+    "mediumblue" => (0x00, 0x00, 0xcd),
+    "mediumorchid" => (0xba, 0x55, 0xd3),
+    "mediumpurple" => (0x93, 0x70, 0xdb),
+    "mediumseagreen" => (0x3c, 0xb3, 0x71),
+    "mediumslateblue" => (0x7b, 0x68, 0xee),
+    "mediumspringgreen" => (0x00, 0xfa, 0x9a),
+    "mediumturquoise" => (0x48, 0xd1, 0xcc),
+    "mediumvioletred" => (0xc7, 0x15, 0x85),
+    "midnightblue" => (0x19, 0x19, 0x70),
+    "mintcream" => (0xf5, 0xff, 0xfa),
+    "mistyrose" => (0xff, 0xe4, 0xe1),
+    "moccasin" => (0xff, 0xe4, 0xb5),
+    "navajowhite" => (0xff, 0xde, 0xad),
+    "navy" => (0x00, 0x00, 0x80),
+    "oldlace" => (0xfd, 0xf5, 0xe6),
+    "olive" => (0x80, 0x80, 0x00),
+    "olivedrab" => (0x6b, 0x8e, 0x23),
+    "orange" => (0xff, 0xa5, 0x00),
+    "orangered" => (0xff, 0x45, 0x00),
+    "orchid" => (0xda, 0x70, 0xd6),
+    "palegoldenrod" => (0xee, 0xe8, 0xaa),
+    "palegreen" => (0x98, 0xfb, 0x98),
+    "paleturquoise" => (0xaf, 0xee, 0xee),
+    "palevioletred" => (0xdb, 0x70, 0x93),
+    "papayawhip" => (0xff, 0xef, 0xd5),
+    "peachpuff" => (0xff, 0xda, 0xb9),
+    "peru" => (0xcd, 0x85, 0x3f),
+    "pink" => (0xff, 0xc0, 0xcb),
+    "plum" => (0xdd, 0xa0, 0xdd),
+    "powderblue" => (0xb0, 0xe0, 0xe6),
+    "purple" => (0x80, 0x00, 0x80),
+    "rebeccapurple" => (0x66, 0x33, 0x99),
+    "red" => (0xff, 0x00, 0x00),
+    "rosybrown" => (0xbc, 0x8f, 0x8f),
+    "royalblue" => (0x41, 0x69, 0xe1),
+    "saddlebrown" => (0x8b, 0x45, 0x13),
+    "salmon" => (0xfa, 0x80, 0x72),
+    "sandybrown" => (0xf4, 0xa4, 0x60),
+    "seagreen" => (0x2e, 0x8b, 0x57),
+    "seashell" => (0xff, 0xf5, 0xee),
+    "sienna" => (0xa0, 0x52, 0x2d),
+    "silver" => (0xc0, 0xc0, 0xc0),
+    "skyblue" => (0x87, 0xce, 0xeb),
+    "slateblue" => (0x6a, 0x5a, 0xcd),
+    "slategray" | "slategrey" => (0x70, 0x80, 0x90),
+    "snow" => (0xff, 0xfa, 0xfa),
+    "springgreen" => (0x00, 0xff, 0x7f),
+    "steelblue" => (0x46, 0x82, 0xb4),
+    "tan" => (0xd2, 0xb4, 0x8c),
+    "teal" => (0x00, 0x80, 0x80),
+    "thistle" => (0xd8, 0xbf, 0xd8),
+    "tomato" => (0xff, 0x63, 0x47),
+    "turquoise" => (0x40, 0xe0, 0xd0),
+    "violet" => (0xee, 0x82, 0xee),
+    "wheat" => (0xf5, 0xde, 0xb3),
+    "white" => (0xff, 0xff, 0xff),
+    "whitesmoke" => (0xf5, 0xf5, 0xf5),
+    "yellow" => (0xff, 0xff, 0x00),
+    "yellowgreen" => (0x9a, 0xcd, 0x32),
+    _ => return None,
+  })
+}
+
+/// Parse a hex colour or a CSS named colour such as `"hotpink"`.
+fn parse_html_colour(s: &str) -> Result<RGBAColour, ParseError> {
+  if s == "transparent" {
+    return Ok(RGBAColour::new(0, 0, 0, 0));
+  }
+  if let Some((r, g, b)) = css_named_colour(&s.to_lowercase()) {
+    return Ok(RGBAColour::new(r, g, b, 255));
+  }
+  RGBAColour::from_hex(s)
 }
 
 /// ColourGradient allows you to create custom colour gradients for each
@@ -43,6 +340,9 @@ pub struct ColourGradient {
   colours: Vec<RGBAColour>,
   min: f32,
   max: f32,
+  blend: Blend,
+  /// `(band_count, smoothness)`, set by [`ColourGradient::sharp`].
+  bands: Option<(usize, f32)>,
 }
 
 impl ColourGradient {
@@ -51,10 +351,88 @@ impl ColourGradient {
       colours: vec![],
       min: 0.0,
       max: 1.0,
+      blend: Blend::LinearRgb,
+      bands: None,
+    }
+  }
+
+  /// Build a gradient from one of the built-in colour maps.
+  pub fn preset(preset: Preset) -> Self {
+    match preset {
+      Preset::Viridis => Self::viridis(),
+      Preset::Magma => Self::magma(),
+      Preset::Inferno => Self::inferno(),
+      Preset::Turbo => Self::turbo(),
+    }
+  }
+
+  /// The `viridis` colour map: dark purple to teal to yellow.
+  pub fn viridis() -> Self {
+    Self::from_rgb_stops(VIRIDIS)
+  }
+
+  /// The `magma` colour map: black to purple to orange to pale yellow.
+  pub fn magma() -> Self {
+    Self::from_rgb_stops(MAGMA)
+  }
+
+  /// The `inferno` colour map: black to purple to red to pale yellow.
+  pub fn inferno() -> Self {
+    Self::from_rgb_stops(INFERNO)
+  }
+
+  /// The `turbo` colour map: a high-contrast, perceptually even rainbow.
+  pub fn turbo() -> Self {
+    Self::from_rgb_stops(TURBO)
+  }
+
+  /// Build a gradient with one stop per hex or CSS colour string.
+  pub fn from_html_colors(colors: &[&str]) -> Result<Self, ParseError> {
+    let colours = colors.iter().map(|s| parse_html_colour(s)).collect::<Result<Vec<_>, _>>()?;
+    Ok(Self {
+      colours,
+      min: 0.0,
+      max: 1.0,
+      blend: Blend::default(),
+      bands: None,
+    })
+  }
+
+  // These colour maps only carry a handful of anchor points, so linear
+  // RGB interpolation between them would reintroduce the muddy banding
+  // that Blend::Lab exists to avoid; blend in Lab by default instead.
+  fn from_rgb_stops(stops: &[(u8, u8, u8)]) -> Self {
+    Self {
+      colours: stops.iter().map(|&(r, g, b)| RGBAColour::new(r, g, b, 255)).collect(),
+      min: 0.0,
+      max: 1.0,
+      blend: Blend::Lab,
+      bands: None,
     }
   }
 
   pub fn get_colour(&self, value: f32) -> RGBAColour {
+    match self.bands {
+      Some((n, smoothness)) => self.get_colour_inner(self.quantize(value, n, smoothness)),
+      None => self.get_colour_inner(value),
+    }
+  }
+
+  /// Snap `value` towards its band centre by `1.0 - smoothness`.
+  fn quantize(&self, value: f32, n: usize, smoothness: f32) -> f32 {
+    assert!(self.max >= self.min);
+    let range = self.max - self.min;
+    let t = ((value - self.min) / range).clamp(0.0, 1.0);
+
+    let band_width = 1.0 / n as f32;
+    let band_index = (t / band_width).floor().min(n as f32 - 1.0);
+    let band_center = (band_index + 0.5) * band_width;
+    let blended_t = band_center + (t - band_center) * smoothness;
+
+    self.min + blended_t * range
+  }
+
+  fn get_colour_inner(&self, value: f32) -> RGBAColour {
     assert!(self.colours.len() > 1);
     assert!(self.max >= self.min);
 
@@ -67,7 +445,7 @@ impl ColourGradient {
 
     // Get the scaled values and indexes to lookup the colour
     let range = self.max - self.min;
-    let scaled_value = value / range * (self.colours.len() as f32 - 1.0);
+    let scaled_value = (value - self.min) / range * (self.colours.len() as f32 - 1.0);
     let idx_value = scaled_value.floor() as usize;
     let ratio = scaled_value - idx_value as f32;
 
@@ -75,10 +453,20 @@ impl ColourGradient {
     let first = self.colours[idx_value].clone();
     let second = self.colours[idx_value + 1].clone();
 
+    let (r, g, b) = match self.blend {
+      Blend::LinearRgb => (
+        self.interpolate(first.r, second.r, ratio),
+        self.interpolate(first.g, second.g, ratio),
+        self.interpolate(first.b, second.b, ratio),
+      ),
+      Blend::Hsv => Self::blend_hsv(&first, &second, ratio),
+      Blend::Lab => Self::blend_lab(&first, &second, ratio),
+    };
+
     RGBAColour {
-      r: self.interpolate(first.r, second.r, ratio),
-      g: self.interpolate(first.g, second.g, ratio),
-      b: self.interpolate(first.b, second.b, ratio),
+      r,
+      g,
+      b,
       a: self.interpolate(first.a, second.a, ratio),
     }
   }
@@ -91,6 +479,38 @@ impl ColourGradient {
     ((f32::from(finish) - f32::from(start)) * ratio + f32::from(start)).round() as u8
   }
 
+  /// Interpolate `start` to `finish` in HSV, the short way round.
+  fn blend_hsv(start: &RGBAColour, finish: &RGBAColour, ratio: f32) -> (u8, u8, u8) {
+    let (h1, s1, v1) = rgb_to_hsv(start.r, start.g, start.b);
+    let (h2, s2, v2) = rgb_to_hsv(finish.r, finish.g, finish.b);
+
+    let mut diff = h2 - h1;
+    if diff > 180.0 {
+      diff -= 360.0;
+    } else if diff < -180.0 {
+      diff += 360.0;
+    }
+    let mut h = h1 + diff * ratio;
+    h = ((h % 360.0) + 360.0) % 360.0;
+
+    let s = s1 + (s2 - s1) * ratio;
+    let v = v1 + (v2 - v1) * ratio;
+
+    hsv_to_rgb(h, s, v)
+  }
+
+  /// Interpolate `start` to `finish` in CIE Lab.
+  fn blend_lab(start: &RGBAColour, finish: &RGBAColour, ratio: f32) -> (u8, u8, u8) {
+    let (l1, a1, b1) = rgb_to_lab(start.r, start.g, start.b);
+    let (l2, a2, b2) = rgb_to_lab(finish.r, finish.g, finish.b);
+
+    let l = l1 + (l2 - l1) * ratio;
+    let a = a1 + (a2 - a1) * ratio;
+    let b = b1 + (b2 - b1) * ratio;
+
+    lab_to_rgb(l, a, b)
+  }
+
   pub fn set_max(&mut self, max: f32) {
     self.max = max
   }
@@ -98,6 +518,371 @@ impl ColourGradient {
   pub fn set_min(&mut self, min: f32) {
     self.min = min
   }
+
+  /// Select the colour space used by [`ColourGradient::get_colour`].
+  pub fn set_blend_mode(&mut self, blend: Blend) {
+    self.blend = blend
+  }
+
+  /// Return a copy of this gradient quantized into `n` constant-colour
+  /// bands; `smoothness` is `0.0` for hard edges, `1.0` for continuous.
+  pub fn sharp(&self, n: usize, smoothness: f32) -> Self {
+    let mut gradient = self.clone();
+    gradient.bands = Some((n.max(1), smoothness.clamp(0.0, 1.0)));
+    gradient
+  }
+
+  /// Sample `n` colours evenly across `[min, max]`, both ends included.
+  pub fn colors(&self, n: usize) -> Vec<RGBAColour> {
+    match n {
+      0 => vec![],
+      1 => vec![self.get_colour(self.min)],
+      _ => (0..n)
+        .map(|i| self.get_colour(self.min + (self.max - self.min) * i as f32 / (n - 1) as f32))
+        .collect(),
+    }
+  }
+
+  /// Parse a GIMP gradient (`.ggr`) file, returning the gradient
+  /// together with its declared name, substituting `fg`/`bg` for the
+  /// special endpoint colours. Segment positions are approximated by
+  /// resampling into [`GGR_RESOLUTION`] evenly spaced stops.
+  pub fn from_ggr<R: BufRead>(reader: R, fg: RGBAColour, bg: RGBAColour) -> Result<(Self, String), ParseError> {
+    let mut lines = reader.lines();
+
+    let header = lines
+      .next()
+      .ok_or_else(|| ParseError::InvalidFormat("empty file".to_string()))?
+      .map_err(|e| ParseError::InvalidFormat(e.to_string()))?;
+    if header.trim() != "GIMP Gradient" {
+      return Err(ParseError::InvalidFormat(format!(
+        "expected 'GIMP Gradient' header, found '{}'",
+        header
+      )));
+    }
+
+    let mut line = lines
+      .next()
+      .ok_or_else(|| ParseError::InvalidFormat("missing segment count".to_string()))?
+      .map_err(|e| ParseError::InvalidFormat(e.to_string()))?;
+
+    let name = if let Some(rest) = line.trim().strip_prefix("Name:") {
+      let name = rest.trim().to_string();
+      line = lines
+        .next()
+        .ok_or_else(|| ParseError::InvalidFormat("missing segment count".to_string()))?
+        .map_err(|e| ParseError::InvalidFormat(e.to_string()))?;
+      name
+    } else {
+      String::new()
+    };
+
+    let segment_count: usize = line
+      .trim()
+      .parse()
+      .map_err(|_| ParseError::InvalidFormat(format!("invalid segment count: '{}'", line)))?;
+    if segment_count == 0 {
+      return Err(ParseError::InvalidFormat("gradient has no segments".to_string()));
+    }
+
+    let mut segments = Vec::with_capacity(segment_count);
+    for i in 0..segment_count {
+      let line = lines
+        .next()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("missing segment line {}", i)))?
+        .map_err(|e| ParseError::InvalidFormat(e.to_string()))?;
+      let segment = parse_ggr_segment(&line, &fg, &bg)
+        .ok_or_else(|| ParseError::InvalidFormat(format!("malformed segment line {}: '{}'", i, line)))?;
+      segments.push(segment);
+    }
+
+    let colours = (0..GGR_RESOLUTION)
+      .map(|i| sample_ggr_segments(&segments, i as f32 / (GGR_RESOLUTION - 1) as f32))
+      .collect();
+
+    Ok((
+      Self {
+        colours,
+        min: 0.0,
+        max: 1.0,
+        blend: Blend::default(),
+        bands: None,
+      },
+      name,
+    ))
+  }
+
+  /// Precompute a [`ColourGradientLut`] with `resolution` entries.
+  pub fn to_lut(&self, resolution: usize) -> ColourGradientLut {
+    assert!(resolution > 1);
+    let entries = (0..resolution)
+      .map(|i| self.get_colour(self.min + (self.max - self.min) * i as f32 / (resolution - 1) as f32))
+      .collect();
+
+    ColourGradientLut {
+      entries,
+      min: self.min,
+      max: self.max,
+    }
+  }
+}
+
+/// A precomputed lookup table over a [`ColourGradient`], for fast
+/// repeated per-pixel lookups. Built with [`ColourGradient::to_lut`].
+#[derive(Clone, Debug)]
+pub struct ColourGradientLut {
+  entries: Vec<RGBAColour>,
+  min: f32,
+  max: f32,
+}
+
+impl ColourGradientLut {
+  pub fn get_colour(&self, value: f32) -> &RGBAColour {
+    let t = ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0);
+    let idx = (t * (self.entries.len() - 1) as f32).round() as usize;
+    &self.entries[idx]
+  }
+}
+
+/// Number of evenly spaced stops `from_ggr` resamples a `.ggr` file's
+/// segments into, so that segment positions are approximated rather
+/// than discarded.
+const GGR_RESOLUTION: usize = 257;
+
+/// One `.ggr` segment: the endpoint colours and their positions along
+/// the `0.0..=1.0` gradient axis.
+struct GgrSegment {
+  left_pos: f32,
+  left_colour: RGBAColour,
+  right_pos: f32,
+  right_colour: RGBAColour,
+}
+
+/// Parse one `.ggr` segment line into a [`GgrSegment`].
+fn parse_ggr_segment(line: &str, fg: &RGBAColour, bg: &RGBAColour) -> Option<GgrSegment> {
+  let fields: Vec<&str> = line.split_whitespace().collect();
+  if fields.len() != 13 && fields.len() != 15 {
+    return None;
+  }
+
+  let left_pos: f32 = fields[0].parse().ok()?;
+  let right_pos: f32 = fields[2].parse().ok()?;
+
+  let f = |i: usize| fields[i].parse::<f32>().ok();
+  let floats: Option<Vec<f32>> = (3..11).map(f).collect();
+  let floats = floats?;
+
+  // Only linear blending (function code 0) in plain RGB (colouring type
+  // code 0) is supported; curved/sine/sphere segments and HSV-direction
+  // segments would be mis-rendered as linear RGB, so reject them.
+  let blending_function: f32 = fields[11].parse().ok()?;
+  let colouring_type: f32 = fields[12].parse().ok()?;
+  if blending_function != 0.0 || colouring_type != 0.0 {
+    return None;
+  }
+
+  let left_rgba = to_rgba(floats[0], floats[1], floats[2], floats[3]);
+  let right_rgba = to_rgba(floats[4], floats[5], floats[6], floats[7]);
+
+  let left_colour = if fields.len() == 15 {
+    match fields[13].parse::<u8>().ok()? {
+      0 => left_rgba,
+      1 => fg.clone(),
+      2 => with_alpha(fg, 0),
+      3 => bg.clone(),
+      4 => with_alpha(bg, 0),
+      _ => return None,
+    }
+  } else {
+    left_rgba
+  };
+
+  let right_colour = if fields.len() == 15 {
+    match fields[14].parse::<u8>().ok()? {
+      0 => right_rgba,
+      1 => fg.clone(),
+      2 => with_alpha(fg, 0),
+      3 => bg.clone(),
+      4 => with_alpha(bg, 0),
+      _ => return None,
+    }
+  } else {
+    right_rgba
+  };
+
+  Some(GgrSegment {
+    left_pos,
+    left_colour,
+    right_pos,
+    right_colour,
+  })
+}
+
+/// Sample the colour at position `t` across a sequence of contiguous
+/// `.ggr` segments, linearly interpolating within whichever segment
+/// contains `t`. A segment's midpoint is not used: each segment blends
+/// evenly from its left to its right colour.
+fn sample_ggr_segments(segments: &[GgrSegment], t: f32) -> RGBAColour {
+  let segment = segments
+    .iter()
+    .find(|s| t >= s.left_pos && t <= s.right_pos)
+    .unwrap_or_else(|| segments.last().expect("from_ggr rejects zero-segment files"));
+
+  let width = segment.right_pos - segment.left_pos;
+  let frac = if width > 0.0 { (t - segment.left_pos) / width } else { 0.0 };
+
+  let lerp = |start: u8, finish: u8| ((f32::from(finish) - f32::from(start)) * frac + f32::from(start)).round() as u8;
+  RGBAColour::new(
+    lerp(segment.left_colour.r, segment.right_colour.r),
+    lerp(segment.left_colour.g, segment.right_colour.g),
+    lerp(segment.left_colour.b, segment.right_colour.b),
+    lerp(segment.left_colour.a, segment.right_colour.a),
+  )
+}
+
+fn to_rgba(r: f32, g: f32, b: f32, a: f32) -> RGBAColour {
+  let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+  RGBAColour::new(to_u8(r), to_u8(g), to_u8(b), to_u8(a))
+}
+
+/// Return `colour` with its alpha channel replaced by `alpha`.
+fn with_alpha(colour: &RGBAColour, alpha: u8) -> RGBAColour {
+  RGBAColour::new(colour.r, colour.g, colour.b, alpha)
+}
+
+/// Convert an 8-bit sRGB colour to (hue in degrees, saturation, value).
+fn rgb_to_hsv(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+  let r = f32::from(r) / 255.0;
+  let g = f32::from(g) / 255.0;
+  let b = f32::from(b) / 255.0;
+
+  let max = r.max(g).max(b);
+  let min = r.min(g).min(b);
+  let delta = max - min;
+
+  let h = if delta == 0.0 {
+    0.0
+  } else if max == r {
+    60.0 * (((g - b) / delta) % 6.0)
+  } else if max == g {
+    60.0 * ((b - r) / delta + 2.0)
+  } else {
+    60.0 * ((r - g) / delta + 4.0)
+  };
+  let h = if h < 0.0 { h + 360.0 } else { h };
+
+  let s = if max == 0.0 { 0.0 } else { delta / max };
+  let v = max;
+
+  (h, s, v)
+}
+
+/// Convert (hue in degrees, saturation, value) back to 8-bit sRGB.
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
+  let c = v * s;
+  let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+  let m = v - c;
+
+  let (r1, g1, b1) = if h < 60.0 {
+    (c, x, 0.0)
+  } else if h < 120.0 {
+    (x, c, 0.0)
+  } else if h < 180.0 {
+    (0.0, c, x)
+  } else if h < 240.0 {
+    (0.0, x, c)
+  } else if h < 300.0 {
+    (x, 0.0, c)
+  } else {
+    (c, 0.0, x)
+  };
+
+  (
+    (((r1 + m) * 255.0).round()) as u8,
+    (((g1 + m) * 255.0).round()) as u8,
+    (((b1 + m) * 255.0).round()) as u8,
+  )
+}
+
+/// The D65 reference white, used by [`rgb_to_lab`] and [`lab_to_rgb`].
+const WHITE_XN: f32 = 95.0489;
+const WHITE_YN: f32 = 100.0;
+const WHITE_ZN: f32 = 108.884;
+
+fn srgb_to_linear(c: f32) -> f32 {
+  if c <= 0.04045 {
+    c / 12.92
+  } else {
+    ((c + 0.055) / 1.055).powf(2.4)
+  }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+  if c <= 0.0031308 {
+    c * 12.92
+  } else {
+    1.055 * c.powf(1.0 / 2.4) - 0.055
+  }
+}
+
+fn lab_f(t: f32) -> f32 {
+  const DELTA: f32 = 6.0 / 29.0;
+  if t > DELTA.powi(3) {
+    t.cbrt()
+  } else {
+    t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+  }
+}
+
+fn lab_f_inv(t: f32) -> f32 {
+  const DELTA: f32 = 6.0 / 29.0;
+  if t > DELTA {
+    t.powi(3)
+  } else {
+    3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+  }
+}
+
+/// Convert an 8-bit sRGB colour to CIE Lab (D65 white point).
+fn rgb_to_lab(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+  let r = srgb_to_linear(f32::from(r) / 255.0);
+  let g = srgb_to_linear(f32::from(g) / 255.0);
+  let b = srgb_to_linear(f32::from(b) / 255.0);
+
+  let x = (r * 0.4124564 + g * 0.3575761 + b * 0.1804375) * 100.0;
+  let y = (r * 0.2126729 + g * 0.7151522 + b * 0.0721750) * 100.0;
+  let z = (r * 0.0193339 + g * 0.119192 + b * 0.9503041) * 100.0;
+
+  let fx = lab_f(x / WHITE_XN);
+  let fy = lab_f(y / WHITE_YN);
+  let fz = lab_f(z / WHITE_ZN);
+
+  let l = 116.0 * fy - 16.0;
+  let a = 500.0 * (fx - fy);
+  let b = 200.0 * (fy - fz);
+
+  (l, a, b)
+}
+
+/// Convert CIE Lab (D65 white point) back to 8-bit sRGB.
+fn lab_to_rgb(l: f32, a: f32, b: f32) -> (u8, u8, u8) {
+  let fy = (l + 16.0) / 116.0;
+  let fx = fy + a / 500.0;
+  let fz = fy - b / 200.0;
+
+  let x = WHITE_XN * lab_f_inv(fx) / 100.0;
+  let y = WHITE_YN * lab_f_inv(fy) / 100.0;
+  let z = WHITE_ZN * lab_f_inv(fz) / 100.0;
+
+  let r = linear_to_srgb(x * 3.2404542 + y * -1.5371385 + z * -0.4985314);
+  let g = linear_to_srgb(x * -0.969266 + y * 1.8760108 + z * 0.0415560);
+  let b = linear_to_srgb(x * 0.0556434 + y * -0.2040259 + z * 1.0572252);
+
+  (
+    (r.clamp(0.0, 1.0) * 255.0).round() as u8,
+    (g.clamp(0.0, 1.0) * 255.0).round() as u8,
+    (b.clamp(0.0, 1.0) * 255.0).round() as u8,
+  )
 }
 
 
@@ -129,4 +914,253 @@ mod tests {
     assert_eq!(gradient.get_colour(0.75), RGBAColour::new(128, 128, 128, 255));
   }
 
+  #[test]
+  fn get_colour_hsv() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(255, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(0, 0, 255, 255));
+    gradient.set_blend_mode(Blend::Hsv);
+
+    // Red to blue the short way round the hue wheel passes through
+    // magenta, not through green.
+    let mid = gradient.get_colour(0.5);
+    assert_eq!(mid, RGBAColour::new(255, 0, 255, 255));
+  }
+
+  #[test]
+  fn get_colour_lab() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    gradient.set_blend_mode(Blend::Lab);
+
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(255, 255, 255, 255));
+  }
+
+  #[test]
+  fn from_ggr_basic() {
+    let ggr = "GIMP Gradient\nName: Black to White\n1\n0.000000 0.500000 1.000000 0.000000 0.000000 0.000000 1.000000 1.000000 1.000000 1.000000 1.000000 0 0\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    let (gradient, name) = ColourGradient::from_ggr(ggr.as_bytes(), fg, bg).unwrap();
+    assert_eq!(name, "Black to White");
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(255, 255, 255, 255));
+  }
+
+  #[test]
+  fn from_ggr_invalid_header() {
+    let ggr = "Not A Gradient\n1\n0.0 0.5 1.0 0 0 0 1 1 1 1 1 0 0\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    assert!(ColourGradient::from_ggr(ggr.as_bytes(), fg, bg).is_err());
+  }
+
+  #[test]
+  fn from_ggr_fg_bg_substitution() {
+    // Extended 15-field form: left endpoint is colour type 1
+    // (foreground), right endpoint is colour type 3 (background). The
+    // literal RGBA values (0 0 0 1 / 0 0 0 1) should be ignored.
+    let ggr = "GIMP Gradient\n1\n0.000000 0.500000 1.000000 0 0 0 1 0 0 0 1 0 0 1 3\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    let (gradient, _) = ColourGradient::from_ggr(ggr.as_bytes(), fg.clone(), bg.clone()).unwrap();
+    assert_eq!(gradient.get_colour(0.0), fg);
+    assert_eq!(gradient.get_colour(1.0), bg);
+  }
+
+  #[test]
+  fn from_ggr_multi_segment() {
+    let ggr = "GIMP Gradient\n2\n\
+               0.000000 0.250000 0.500000 0 0 0 1 1 1 1 1 0 0\n\
+               0.500000 0.750000 1.000000 1 1 1 1 0 0 0 1 0 0\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    let (gradient, _) = ColourGradient::from_ggr(ggr.as_bytes(), fg, bg).unwrap();
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(gradient.get_colour(0.5), RGBAColour::new(255, 255, 255, 255));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(0, 0, 0, 255));
+  }
+
+  #[test]
+  fn from_ggr_rejects_unsupported_blending() {
+    // Blending function code 2 (sine) is not linear and must be rejected
+    // rather than silently mis-rendered as linear.
+    let ggr = "GIMP Gradient\n1\n0.0 0.5 1.0 0 0 0 1 1 1 1 1 2 0\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    assert!(ColourGradient::from_ggr(ggr.as_bytes(), fg, bg).is_err());
+  }
+
+  #[test]
+  fn from_ggr_transparent_fg_bg_substitution() {
+    // Colour type 2 / 4 substitute the foreground/background colour
+    // with its alpha forced to 0, rather than reusing fg/bg verbatim.
+    let ggr = "GIMP Gradient\n1\n0.000000 0.500000 1.000000 0 0 0 1 0 0 0 1 0 0 2 4\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    let (gradient, _) = ColourGradient::from_ggr(ggr.as_bytes(), fg, bg).unwrap();
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(255, 0, 0, 0));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(0, 255, 0, 0));
+  }
+
+  #[test]
+  fn from_ggr_non_uniform_segment_widths() {
+    // A narrow first segment (0.0..0.1) followed by a wide second
+    // segment (0.1..1.0) must keep its width: `get_colour(0.1)` should
+    // land near the segment boundary's red, not be flattened towards
+    // the midpoint of an evenly divided gradient.
+    let ggr = "GIMP Gradient\n2\n\
+               0.000000 0.050000 0.100000 0 0 0 1 1 0 0 1 0 0\n\
+               0.100000 0.550000 1.000000 1 0 0 1 1 1 1 1 0 0\n";
+    let fg = RGBAColour::new(255, 0, 0, 255);
+    let bg = RGBAColour::new(0, 255, 0, 255);
+
+    let (gradient, _) = ColourGradient::from_ggr(ggr.as_bytes(), fg, bg).unwrap();
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(gradient.get_colour(0.1), RGBAColour::new(253, 0, 0, 255));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(255, 255, 255, 255));
+  }
+
+  #[test]
+  fn preset_endpoints() {
+    let gradient = ColourGradient::preset(Preset::Viridis);
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(0x44, 0x01, 0x54, 255));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(0xfd, 0xe7, 0x25, 255));
+
+    let turbo = ColourGradient::turbo();
+    assert_eq!(turbo.get_colour(0.0), RGBAColour::new(0x30, 0x12, 0x3b, 255));
+    assert_eq!(turbo.get_colour(1.0), RGBAColour::new(0x7a, 0x04, 0x03, 255));
+  }
+
+  #[test]
+  fn from_hex() {
+    assert_eq!(RGBAColour::from_hex("#fff").unwrap(), RGBAColour::new(255, 255, 255, 255));
+    assert_eq!(RGBAColour::from_hex("0f08").unwrap(), RGBAColour::new(0, 255, 0, 136));
+    assert_eq!(RGBAColour::from_hex("#ff0000").unwrap(), RGBAColour::new(255, 0, 0, 255));
+    assert_eq!(
+      RGBAColour::from_hex("#ff000080").unwrap(),
+      RGBAColour::new(255, 0, 0, 128)
+    );
+    assert!(RGBAColour::from_hex("#zzz").is_err());
+    assert!(RGBAColour::from_hex("#12345").is_err());
+  }
+
+  #[test]
+  fn from_html_colors() {
+    let gradient = ColourGradient::from_html_colors(&["hotpink", "#ffd700", "darkturquoise"]).unwrap();
+    assert_eq!(gradient.get_colour(0.0), RGBAColour::new(0xff, 0x69, 0xb4, 255));
+    assert_eq!(gradient.get_colour(1.0), RGBAColour::new(0x00, 0xce, 0xd1, 255));
+
+    assert!(ColourGradient::from_html_colors(&["not-a-colour"]).is_err());
+  }
+
+  #[test]
+  fn sharp_hard_edges() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    let gradient = gradient.sharp(2, 0.0);
+
+    // Every point within a band resolves to that band's flat colour.
+    assert_eq!(gradient.get_colour(0.1), gradient.get_colour(0.4));
+    assert_eq!(gradient.get_colour(0.6), gradient.get_colour(0.9));
+    assert_ne!(gradient.get_colour(0.25), gradient.get_colour(0.75));
+  }
+
+  #[test]
+  fn sharp_smoothness_one_matches_continuous() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    let sharp = gradient.sharp(4, 1.0);
+
+    for i in 0..=10 {
+      let value = i as f32 / 10.0;
+      assert_eq!(sharp.get_colour(value), gradient.get_colour(value));
+    }
+  }
+
+  #[test]
+  fn sharp_non_default_domain() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    gradient.set_min(10.0);
+    gradient.set_max(20.0);
+    let sharp = gradient.sharp(2, 0.0);
+
+    assert_eq!(sharp.get_colour(12.0), RGBAColour::new(64, 64, 64, 255));
+    assert_eq!(sharp.get_colour(18.0), RGBAColour::new(191, 191, 191, 255));
+  }
+
+  #[test]
+  fn colors_samples_inclusive_endpoints() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+
+    assert_eq!(gradient.colors(0), Vec::<RGBAColour>::new());
+    assert_eq!(gradient.colors(1), vec![RGBAColour::new(0, 0, 0, 255)]);
+
+    let colors = gradient.colors(5);
+    assert_eq!(colors.len(), 5);
+    assert_eq!(colors[0], RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(colors[4], RGBAColour::new(255, 255, 255, 255));
+    assert_eq!(colors[2], RGBAColour::new(128, 128, 128, 255));
+  }
+
+  #[test]
+  fn colors_non_default_domain() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    gradient.set_min(10.0);
+    gradient.set_max(20.0);
+
+    let colors = gradient.colors(3);
+    assert_eq!(colors[0], RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(colors[1], RGBAColour::new(128, 128, 128, 255));
+    assert_eq!(colors[2], RGBAColour::new(255, 255, 255, 255));
+  }
+
+  #[test]
+  fn to_bytes() {
+    assert_eq!(RGBAColour::new(1, 2, 3, 4).to_bytes(), [1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn to_lut_matches_get_colour() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    let lut = gradient.to_lut(256);
+
+    assert_eq!(*lut.get_colour(0.0), gradient.get_colour(0.0));
+    assert_eq!(*lut.get_colour(1.0), gradient.get_colour(1.0));
+    // Values outside the domain clamp to the nearest endpoint.
+    assert_eq!(*lut.get_colour(-1.0), gradient.get_colour(0.0));
+    assert_eq!(*lut.get_colour(2.0), gradient.get_colour(1.0));
+  }
+
+  #[test]
+  fn to_lut_non_default_domain() {
+    let mut gradient = ColourGradient::new();
+    gradient.add_colour(RGBAColour::new(0, 0, 0, 255));
+    gradient.add_colour(RGBAColour::new(255, 255, 255, 255));
+    gradient.set_min(10.0);
+    gradient.set_max(20.0);
+    let lut = gradient.to_lut(11);
+
+    assert_eq!(*lut.get_colour(10.0), RGBAColour::new(0, 0, 0, 255));
+    assert_eq!(*lut.get_colour(15.0), RGBAColour::new(128, 128, 128, 255));
+    assert_eq!(*lut.get_colour(20.0), RGBAColour::new(255, 255, 255, 255));
+  }
 }
\ No newline at end of file